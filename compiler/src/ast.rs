@@ -1,9 +1,35 @@
 use crate::effects::Effect;
+use crate::lexer::Span;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone)]
 pub enum Item {
-    Actor { name: String },
-    Func { name: String },
+    Actor {
+        name: String,
+        /// Effects this actor's body performs, inferred by the parser, keyed
+        /// to the span of the first call that performs each one.
+        effects: BTreeMap<Effect, Span>,
+    },
+    Func {
+        name: String,
+        /// Effects this function's body performs, inferred by the parser, keyed
+        /// to the span of the first call that performs each one.
+        effects: BTreeMap<Effect, Span>,
+    },
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Actor { name, .. } | Item::Func { name, .. } => name,
+        }
+    }
+
+    pub fn effects(&self) -> &BTreeMap<Effect, Span> {
+        match self {
+            Item::Actor { effects, .. } | Item::Func { effects, .. } => effects,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
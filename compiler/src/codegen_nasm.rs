@@ -0,0 +1,94 @@
+use crate::ast::Module;
+use crate::effects::Effect;
+use crate::ir::{self, Ir};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The runtime shim an effect call lowers to.
+fn shim_for(effect: &Effect) -> &'static str {
+    match effect {
+        Effect::Db => "weft_db_query",
+        Effect::Net => "weft_net_fetch",
+        Effect::Now => "weft_now",
+        Effect::Kms => "weft_kms_sign",
+        Effect::Serial => "weft_serial_read",
+    }
+}
+
+/// Lower `module` to x86-64 NASM assembly, then assemble and link it into a
+/// native binary under `out_dir`. Returns the path to the produced binary.
+pub fn build(module: &Module, out_dir: &str) -> Result<PathBuf> {
+    let lowered = ir::lower(module);
+    let asm = emit_asm(&lowered);
+
+    let out_dir = Path::new(out_dir);
+    std::fs::create_dir_all(out_dir)?;
+    let stem = lowered.module_name.replace('.', "_");
+    let asm_path = out_dir.join(format!("{stem}.asm"));
+    std::fs::write(&asm_path, asm)?;
+
+    let obj_path = out_dir.join(format!("{stem}.o"));
+    let status = Command::new("nasm")
+        .args(["-felf64", "-o"])
+        .arg(&obj_path)
+        .arg(&asm_path)
+        .status()
+        .context("failed to run nasm; is it installed?")?;
+    if !status.success() {
+        bail!("nasm failed to assemble {}", asm_path.display());
+    }
+
+    let bin_path = out_dir.join(stem);
+    let status = Command::new("cc")
+        .arg(&obj_path)
+        .arg("-o")
+        .arg(&bin_path)
+        .arg("-no-pie")
+        .arg("-nostartfiles")
+        .status()
+        .context("failed to run cc linker")?;
+    if !status.success() {
+        bail!("linking failed for {}", obj_path.display());
+    }
+
+    Ok(bin_path)
+}
+
+/// Emit a `global _start` NASM module: one label per lowered function, each
+/// calling into `extern` runtime shims for the effects it performs with
+/// zeroed System V AMD64 argument registers (rdi, rsi, rdx) until argument
+/// lowering lands, followed by a `_start` that calls every function in
+/// order and exits via `sys_exit`.
+fn emit_asm(ir: &Ir) -> String {
+    let mut out = String::new();
+    let shims: BTreeSet<&'static str> = ir
+        .funcs
+        .iter()
+        .flat_map(|f| f.calls.iter().map(shim_for))
+        .collect();
+    for shim in &shims {
+        out.push_str(&format!("extern {}\n", shim));
+    }
+    out.push_str("global _start\n\n");
+    out.push_str("section .text\n");
+    for func in &ir.funcs {
+        out.push_str(&format!("{}:\n", func.name));
+        for effect in &func.calls {
+            out.push_str("    xor edi, edi\n");
+            out.push_str("    xor esi, esi\n");
+            out.push_str("    xor edx, edx\n");
+            out.push_str(&format!("    call {}\n", shim_for(effect)));
+        }
+        out.push_str("    ret\n\n");
+    }
+    out.push_str("_start:\n");
+    for func in &ir.funcs {
+        out.push_str(&format!("    call {}\n", func.name));
+    }
+    out.push_str("    mov eax, 60\n");
+    out.push_str("    xor edi, edi\n");
+    out.push_str("    syscall\n");
+    out
+}
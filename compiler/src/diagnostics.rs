@@ -0,0 +1,131 @@
+use crate::lexer::Span;
+
+/// How severe a diagnostic is; controls the header colour and label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Severity::Error => "\x1b[31;1m",  // bold red
+            Severity::Warning => "\x1b[33;1m", // bold yellow
+        }
+    }
+}
+
+/// A secondary span with a short note, rendered under its own source line.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub text: String,
+}
+
+impl Label {
+    pub fn new(span: Span, text: impl Into<String>) -> Self {
+        Self {
+            span,
+            text: text.into(),
+        }
+    }
+}
+
+/// A renderable compiler diagnostic: a primary span, optional secondary
+/// labels, and an optional help note. `ParseError`/`TypeError` convert into
+/// this to share one rendering path.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+            labels: vec![],
+            help: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+/// Locate the 1-based line containing `offset` and return `(line_no, line_text, line_start_offset)`.
+fn line_containing(src: &str, offset: usize) -> (usize, &str, usize) {
+    let offset = offset.min(src.len());
+    let mut line_no = 1;
+    let mut line_start = 0;
+    for (i, b) in src.as_bytes().iter().enumerate() {
+        if i >= offset {
+            break;
+        }
+        if *b == b'\n' {
+            line_no += 1;
+            line_start = i + 1;
+        }
+    }
+    let line_end = src[line_start..]
+        .find('\n')
+        .map(|n| line_start + n)
+        .unwrap_or(src.len());
+    (line_no, &src[line_start..line_end], line_start)
+}
+
+fn render_span(src: &str, span: Span, gutter: usize, marker: char, out: &mut String) {
+    let (line_no, text, line_start) = line_containing(src, span.start);
+    let rel_start = span.start.saturating_sub(line_start).min(text.len());
+    let rel_end = span.end.saturating_sub(line_start).clamp(rel_start, text.len()).max(rel_start + 1);
+    out.push_str(&format!("{:>width$} | {}\n", line_no, text, width = gutter));
+    out.push_str(&format!("{:>width$} | ", "", width = gutter));
+    out.push_str(&" ".repeat(rel_start));
+    out.push_str(&marker.to_string().repeat((rel_end - rel_start).max(1)));
+    out.push('\n');
+}
+
+/// Render a diagnostic ariadne/codespan-style: a severity header, the
+/// primary line with a `^^^` underline under the span, each secondary
+/// label's line with a `---` underline and its note, and a trailing help.
+pub fn render(src: &str, diag: &Diagnostic) -> String {
+    const RESET: &str = "\x1b[0m";
+    let gutter = 4usize;
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{}{}{}: {}\n",
+        diag.severity.color(),
+        diag.severity.label(),
+        RESET,
+        diag.message
+    ));
+    render_span(src, diag.span, gutter, '^', &mut out);
+    for label in &diag.labels {
+        render_span(src, label.span, gutter, '-', &mut out);
+        out.push_str(&format!("{:>width$} | {}\n", "", label.text, width = gutter));
+    }
+    if let Some(help) = &diag.help {
+        out.push_str(&format!("help: {}\n", help));
+    }
+    out
+}
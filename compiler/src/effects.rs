@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Effect {
     Db,
     Net,
@@ -20,10 +20,17 @@ impl Effect {
     }
 }
 
+/// Real effect edges derived from inferred per-item usage, not just the
+/// module's declared `effects` list: one `(item_name, effect)` pair for
+/// every effect an item's body actually performs.
 pub fn effects_graph(m: &crate::ast::Module) -> Vec<(String, Effect)> {
-    m.effects
+    m.items
         .iter()
-        .cloned()
-        .map(|e| (m.name.clone(), e))
+        .flat_map(|item| {
+            item.effects()
+                .keys()
+                .cloned()
+                .map(|e| (item.name().to_string(), e))
+        })
         .collect()
 }
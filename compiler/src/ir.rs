@@ -0,0 +1,40 @@
+use crate::ast::{Item, Module};
+use crate::effects::Effect;
+
+/// One lowered function: its name and the distinct effects its body performs,
+/// one entry per effect, ordered as the `Effect` variants are declared. The
+/// native backend lowers from this; the wasm and vm targets of `cmd_build`
+/// only report how many functions were lowered.
+#[derive(Debug, Clone)]
+pub struct IrFunc {
+    pub name: String,
+    pub calls: Vec<Effect>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ir {
+    pub module_name: String,
+    pub funcs: Vec<IrFunc>,
+}
+
+/// Lower a parsed, typechecked `Module` into the shared IR. Only
+/// `Item::Func`s become callables here; actors aren't plain functions and
+/// have no lowering of their own yet, so they're left out rather than
+/// getting a spurious NASM label and a direct call from `_start`.
+pub fn lower(module: &Module) -> Ir {
+    let funcs = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Func { name, effects } => Some(IrFunc {
+                name: name.clone(),
+                calls: effects.keys().cloned().collect(),
+            }),
+            Item::Actor { .. } => None,
+        })
+        .collect();
+    Ir {
+        module_name: module.name.clone(),
+        funcs,
+    }
+}
@@ -1,22 +1,15 @@
-<<<<<<< HEAD
-<<<<<<< HEAD
-pub fn lex(_input: &str) {}
-=======
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Token {
-    Ident(String),
-    Number(String),
-    LParen, RParen, LBrace, RBrace,
-    Arrow, Colon, Semicolon, Comma,
-    Eof,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
 }
 
-pub fn lex(_src: &str) -> Vec<Token> {
-    // Minimal stub; real lexer lands in Step 2.
-    vec![Token::Eof]
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
 }
->>>>>>> origin/pybde0-codex/create-top-level-repo-layout
-=======
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokKind {
     Ident(String),
@@ -38,18 +31,27 @@ pub enum TokKind {
     Eq,
     Eof,
 }
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub kind: TokKind,
     pub line: usize,
     pub col: usize,
+    pub span: Span,
 }
 
 pub fn lex(src: &str) -> Vec<Token> {
     let mut t = Vec::new();
     let (mut i, mut line, mut col) = (0usize, 1usize, 1usize);
     let b = src.as_bytes();
-    let push = |kind: TokKind, line, col, t: &mut Vec<Token>| t.push(Token { kind, line, col });
+    let push = |kind: TokKind, line, col, start, end, t: &mut Vec<Token>| {
+        t.push(Token {
+            kind,
+            line,
+            col,
+            span: Span::new(start, end),
+        })
+    };
     while i < b.len() {
         let c = b[i] as char;
         match c {
@@ -69,57 +71,57 @@ pub fn lex(src: &str) -> Vec<Token> {
                 }
             }
             '(' => {
-                push(TokKind::LParen, line, col, &mut t);
+                push(TokKind::LParen, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             ')' => {
-                push(TokKind::RParen, line, col, &mut t);
+                push(TokKind::RParen, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             '{' => {
-                push(TokKind::LBrace, line, col, &mut t);
+                push(TokKind::LBrace, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             '}' => {
-                push(TokKind::RBrace, line, col, &mut t);
+                push(TokKind::RBrace, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             ',' => {
-                push(TokKind::Comma, line, col, &mut t);
+                push(TokKind::Comma, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             ':' => {
-                push(TokKind::Colon, line, col, &mut t);
+                push(TokKind::Colon, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             ';' => {
-                push(TokKind::Semicolon, line, col, &mut t);
+                push(TokKind::Semicolon, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             '.' => {
-                push(TokKind::Dot, line, col, &mut t);
+                push(TokKind::Dot, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             '-' if i + 1 < b.len() && b[i + 1] as char == '>' => {
-                push(TokKind::Arrow, line, col, &mut t);
+                push(TokKind::Arrow, line, col, i, i + 2, &mut t);
                 i += 2;
                 col += 2;
             }
             '=' => {
-                push(TokKind::Eq, line, col, &mut t);
+                push(TokKind::Eq, line, col, i, i + 1, &mut t);
                 i += 1;
                 col += 1;
             }
             '"' => {
-                let (start_l, start_c) = (line, col);
+                let (start_l, start_c, start_i) = (line, col, i);
                 i += 1;
                 col += 1;
                 let mut s = String::new();
@@ -136,10 +138,10 @@ pub fn lex(src: &str) -> Vec<Token> {
                 }
                 i += 1;
                 col += 1;
-                push(TokKind::String(s), start_l, start_c, &mut t);
+                push(TokKind::String(s), start_l, start_c, start_i, i, &mut t);
             }
             c if c.is_ascii_alphabetic() || c == '_' => {
-                let (sl, sc) = (line, col);
+                let (sl, sc, si) = (line, col, i);
                 let mut s = String::new();
                 while i < b.len() {
                     let ch = b[i] as char;
@@ -158,17 +160,17 @@ pub fn lex(src: &str) -> Vec<Token> {
                     "fn" => TokKind::Fn,
                     _ => TokKind::Ident(s),
                 };
-                push(kind, sl, sc, &mut t);
+                push(kind, sl, sc, si, i, &mut t);
             }
             c if c.is_ascii_digit() => {
-                let (sl, sc) = (line, col);
+                let (sl, sc, si) = (line, col, i);
                 let mut s = String::new();
                 while i < b.len() && (b[i] as char).is_ascii_digit() {
                     s.push(b[i] as char);
                     i += 1;
                     col += 1;
                 }
-                push(TokKind::Number(s), sl, sc, &mut t);
+                push(TokKind::Number(s), sl, sc, si, i, &mut t);
             }
             _ => {
                 i += 1;
@@ -180,7 +182,7 @@ pub fn lex(src: &str) -> Vec<Token> {
         kind: TokKind::Eof,
         line,
         col,
+        span: Span::new(i, i),
     });
     t
 }
->>>>>>> origin/w5t1y7-codex/create-top-level-repo-layout
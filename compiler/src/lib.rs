@@ -0,0 +1,11 @@
+pub mod ast;
+pub mod codegen_nasm;
+pub mod diagnostics;
+pub mod effects;
+pub mod ir;
+pub mod lexer;
+pub mod parser;
+pub mod repl;
+pub mod transpile_ts;
+pub mod transpile_wit;
+pub mod typer;
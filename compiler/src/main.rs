@@ -1,7 +1,9 @@
 use anyhow::{self, Result};
 use clap::{Parser, Subcommand};
 use std::{fs, path::Path};
-use weftc::{effects, ir, lexer, parser, transpile_ts, typer};
+use weftc::{
+    codegen_nasm, diagnostics, effects, ir, lexer, parser, repl, transpile_ts, transpile_wit, typer,
+};
 
 #[derive(Parser)]
 #[command(name = "weftc", version, about = "Weft compiler CLI")]
@@ -20,24 +22,52 @@ enum Commands {
         #[arg(long)]
         out: String,
     },
-    /// Build stub to target: wasm|native|vm
+    /// Emit a WIT world describing the module's effect imports and function exports
+    Wit {
+        file: String,
+        #[arg(long)]
+        out: String,
+    },
+    /// Build to target: wasm|native|vm
     Build {
+        file: String,
         #[arg(long)]
         target: String,
         #[arg(long, default_value = "build/")]
         out: String,
     },
+    /// Start an interactive read-eval-print loop
+    Repl,
 }
 
 fn load_src(p: &str) -> Result<String> {
     Ok(fs::read_to_string(p)?)
 }
 
+/// Render every parse error from one compile pass and turn them into a
+/// single anyhow error for `?` to propagate.
+fn report_parse_errors(src: &str, errors: Vec<parser::ParseError>) -> anyhow::Error {
+    for e in &errors {
+        eprint!("{}", diagnostics::render(src, &e.diagnostic()));
+    }
+    anyhow::anyhow!(
+        "parse failed with {} error{}",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" }
+    )
+}
+
 fn cmd_check(file: &str) -> Result<()> {
     let src = load_src(file)?;
     let toks = lexer::lex(&src);
-    let module = parser::parse(&toks).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-    typer::typecheck(&module).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let module = parser::parse(&toks).map_err(|e| report_parse_errors(&src, e))?;
+    let warnings = typer::typecheck(&module).map_err(|e| {
+        eprint!("{}", diagnostics::render(&src, &e.diagnostic()));
+        anyhow::anyhow!("typecheck failed")
+    })?;
+    for w in &warnings {
+        eprint!("{}", diagnostics::render(&src, w));
+    }
     let graph = effects::effects_graph(&module);
     println!("OK: module={}", module.name);
     println!("Effects: {:?}", module.effects);
@@ -47,7 +77,7 @@ fn cmd_check(file: &str) -> Result<()> {
 
 fn cmd_ts(file: &str, out: &str) -> Result<()> {
     let src = load_src(file)?;
-    let module = parser::parse(&lexer::lex(&src)).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let module = parser::parse(&lexer::lex(&src)).map_err(|e| report_parse_errors(&src, e))?;
     let ts = transpile_ts::emit_ts(&module);
     fs::create_dir_all(Path::new(out).parent().unwrap_or(Path::new(".")))?;
     fs::write(out, ts)?;
@@ -55,14 +85,37 @@ fn cmd_ts(file: &str, out: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_build(target: &str, out: &str) -> Result<()> {
+fn cmd_wit(file: &str, out: &str) -> Result<()> {
+    let src = load_src(file)?;
+    let module = parser::parse(&lexer::lex(&src)).map_err(|e| report_parse_errors(&src, e))?;
+    let wit = transpile_wit::emit_wit(&module);
+    fs::create_dir_all(Path::new(out).parent().unwrap_or(Path::new(".")))?;
+    fs::write(out, wit)?;
+    println!("Wrote WIT world to {}", out);
+    Ok(())
+}
+
+fn cmd_build(file: &str, target: &str, out: &str) -> Result<()> {
     let plan_ok = matches!(target, "wasm" | "native" | "vm");
     if !plan_ok {
         anyhow::bail!("--target must be wasm|native|vm");
     }
     fs::create_dir_all(out)?;
-    let ir = ir::Ir { bytes: 42 };
-    let plan = format!("target={target}, out={out}, ir_size={}", ir.bytes);
+    let src = load_src(file)?;
+    let module = parser::parse(&lexer::lex(&src)).map_err(|e| report_parse_errors(&src, e))?;
+    typer::typecheck(&module).map_err(|e| {
+        eprint!("{}", diagnostics::render(&src, &e.diagnostic()));
+        anyhow::anyhow!("typecheck failed")
+    })?;
+
+    if target == "native" {
+        let bin = codegen_nasm::build(&module, out)?;
+        println!("target=native, out={out}, binary={}", bin.display());
+        return Ok(());
+    }
+
+    let ir = ir::lower(&module);
+    let plan = format!("target={target}, out={out}, ir_funcs={}", ir.funcs.len());
     fs::write(format!("{out}/plan.txt"), &plan)?;
     println!("{}", plan);
     Ok(())
@@ -73,6 +126,8 @@ fn main() -> Result<()> {
     match cli.cmd {
         Commands::Check { file } => cmd_check(&file),
         Commands::TranspileTs { file, out } => cmd_ts(&file, &out),
-        Commands::Build { target, out } => cmd_build(&target, &out),
+        Commands::Wit { file, out } => cmd_wit(&file, &out),
+        Commands::Build { file, target, out } => cmd_build(&file, &target, &out),
+        Commands::Repl => repl::run(),
     }
 }
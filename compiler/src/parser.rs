@@ -1,15 +1,53 @@
 use crate::{
     ast::{Item, Module},
+    diagnostics::{Diagnostic, Label},
     effects::Effect,
-    lexer::{TokKind, Token},
+    lexer::{Span, TokKind, Token},
 };
+use std::collections::BTreeMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParseError {
     pub msg: String,
     pub line: usize,
     pub col: usize,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub help: Option<String>,
 }
+
+impl ParseError {
+    fn new(msg: impl Into<String>, tok: &Token) -> Self {
+        Self {
+            msg: msg.into(),
+            line: tok.line,
+            col: tok.col,
+            span: tok.span,
+            labels: vec![],
+            help: None,
+        }
+    }
+
+    fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut d = Diagnostic::error(self.msg.clone(), self.span);
+        d.labels = self.labels.clone();
+        if let Some(help) = &self.help {
+            d = d.with_help(help.clone());
+        }
+        d
+    }
+}
+
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} at {}:{}", self.msg, self.line, self.col)
@@ -17,173 +55,337 @@ impl std::fmt::Display for ParseError {
 }
 impl std::error::Error for ParseError {}
 
-fn expect(
-    toks: &[Token],
-    i: &mut usize,
-    k: fn(&TokKind) -> bool,
-    what: &str,
-) -> Result<Token, ParseError> {
-    let tok = toks
-        .get(*i)
-        .ok_or(ParseError {
-            msg: format!("expected {}", what),
-            line: 0,
-            col: 0,
-        })?
-        .clone();
-    if k(&tok.kind) {
-        *i += 1;
-        Ok(tok)
+/// The result of one combinator step: its own outcome, every error observed
+/// producing it (including from recovered-from sub-parses), and the token
+/// position to resume from. Composing these is how recovery collects every
+/// error from one compile pass instead of bailing on the first one.
+type PResult<T> = (Result<T, ParseError>, Vec<ParseError>, usize);
+
+fn eof_token(toks: &[Token]) -> Token {
+    toks.last().cloned().unwrap_or(Token {
+        kind: TokKind::Eof,
+        line: 0,
+        col: 0,
+        span: Span::new(0, 0),
+    })
+}
+
+/// Match a single token satisfying `pred`, advancing one position on success.
+fn token(toks: &[Token], pos: usize, pred: impl Fn(&TokKind) -> bool, what: &str) -> PResult<Token> {
+    let tok = toks.get(pos).cloned().unwrap_or_else(|| eof_token(toks));
+    if pred(&tok.kind) {
+        (Ok(tok), vec![], pos + 1)
     } else {
-        Err(ParseError {
-            msg: format!("expected {}", what),
-            line: tok.line,
-            col: tok.col,
-        })
-    }
-}
-
-pub fn parse(toks: &[Token]) -> Result<Module, ParseError> {
-    let mut i = 0usize;
-
-    // module <ident(.ident)*>
-    expect(toks, &mut i, |k| matches!(k, TokKind::Module), "module")?;
-    let mut name = String::new();
-    let first = expect(
-        toks,
-        &mut i,
-        |k| matches!(k, TokKind::Ident(_)),
-        "module ident",
-    )?;
-    if let TokKind::Ident(s) = first.kind {
-        name.push_str(&s);
-    }
-    while matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::Dot)) {
-        i += 1;
-        let id = expect(
-            toks,
-            &mut i,
-            |k| matches!(k, TokKind::Ident(_)),
-            "ident after dot",
-        )?;
-        if let TokKind::Ident(s) = id.kind {
-            name.push('.');
-            name.push_str(&s);
+        let err = ParseError::new(format!("expected {}", what), &tok);
+        (Err(err.clone()), vec![err], pos)
+    }
+}
+
+/// Match a bare identifier, extracting its text.
+fn ident(toks: &[Token], pos: usize, what: &str) -> PResult<String> {
+    let (res, errs, pos) = token(toks, pos, |k| matches!(k, TokKind::Ident(_)), what);
+    let res = res.map(|tok| match tok.kind {
+        TokKind::Ident(s) => s,
+        _ => unreachable!(),
+    });
+    (res, errs, pos)
+}
+
+/// Parse `elem` one-or-more times, separated by `sep`, stopping as soon as
+/// `sep` doesn't follow.
+fn sep_by<T>(
+    toks: &[Token],
+    mut pos: usize,
+    sep: &TokKind,
+    mut elem: impl FnMut(&[Token], usize) -> PResult<T>,
+) -> PResult<Vec<T>> {
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    loop {
+        let (res, errs, new_pos) = elem(toks, pos);
+        errors.extend(errs);
+        pos = new_pos;
+        match res {
+            Ok(v) => items.push(v),
+            Err(e) => return (Err(e), errors, pos),
         }
+        if toks.get(pos).map(|t| &t.kind) == Some(sep) {
+            pos += 1;
+            continue;
+        }
+        break;
     }
-    let mut m = Module::new(name);
+    (Ok(items), errors, pos)
+}
 
-    // optional: effects <Effect (, Effect)*>
-    if matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::Effects)) {
-        i += 1;
-        loop {
-            let e = expect(
-                toks,
-                &mut i,
-                |k| matches!(k, TokKind::Ident(_)),
-                "effect ident",
-            )?;
-            if let TokKind::Ident(s) = e.kind {
-                if let Some(eff) = Effect::from_ident(&s) {
-                    m.effects.push(eff);
-                } else {
-                    return Err(ParseError {
-                        msg: format!("unknown effect '{}'", s),
-                        line: e.line,
-                        col: e.col,
-                    });
-                }
-            }
-            if matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::Comma)) {
-                i += 1;
-                continue;
-            }
-            break;
+/// An alternative passed to `choice`.
+type ChoiceParser<T> = fn(&[Token], usize) -> PResult<T>;
+
+/// Try each alternative in order, returning the first success. An alternative
+/// that fails without consuming any tokens is uncommitted, so the next one is
+/// tried from the same position; one that fails *after* consuming tokens
+/// (e.g. it matched a leading keyword and then failed inside the body) has
+/// committed to that alternative, so the remaining ones — which could never
+/// have matched this input anyway — are not tried, and its error stands
+/// alone instead of stacking with unwinnable "expected X" noise.
+fn choice<T>(parsers: &[ChoiceParser<T>], toks: &[Token], pos: usize) -> PResult<T> {
+    let mut errors = Vec::new();
+    for p in parsers {
+        let (res, errs, new_pos) = p(toks, pos);
+        if res.is_ok() {
+            return (res, errs, new_pos);
+        }
+        if new_pos > pos {
+            // Committed: this alternative's error stands alone, not mixed
+            // with "expected X" noise from prior uncommitted tries.
+            return (res, errs, new_pos);
         }
+        errors.extend(errs);
     }
+    let tok = toks.get(pos).cloned().unwrap_or_else(|| eof_token(toks));
+    let err = ParseError::new("expected an item ('actor' or 'fn')", &tok);
+    errors.push(err.clone());
+    (Err(err), errors, pos)
+}
 
-    // items until EOF: actor <Ident> { ... } | fn <Ident> ...
-    while !matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::Eof)) {
-        match toks.get(i).map(|t| t.kind.clone()) {
-            Some(TokKind::Actor) => {
-                i += 1;
-                let name = expect(
-                    toks,
-                    &mut i,
-                    |k| matches!(k, TokKind::Ident(_)),
-                    "actor name",
-                )?;
-                let nm = if let TokKind::Ident(s) = name.kind {
-                    s
-                } else {
-                    unreachable!()
-                };
-                skip_block(toks, &mut i)?;
-                m.items.push(Item::Actor { name: nm });
-            }
-            Some(TokKind::Fn) => {
-                i += 1;
-                let name = expect(toks, &mut i, |k| matches!(k, TokKind::Ident(_)), "fn name")?;
-                let nm = if let TokKind::Ident(s) = name.kind {
-                    s
-                } else {
-                    unreachable!()
-                };
-                while !matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::LBrace))
-                    && !matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::Eof))
-                {
-                    i += 1;
-                }
-                if matches!(toks.get(i).map(|t| &t.kind), Some(TokKind::LBrace)) {
-                    skip_block(toks, &mut i)?;
-                }
-                m.items.push(Item::Func { name: nm });
-            }
-            Some(_) => {
-                i += 1;
+/// Skip forward to the next synchronization point — a `;`, a `}`, or a
+/// top-level keyword (`actor`/`fn`/EOF) — so the item loop can resume after
+/// a failed item instead of aborting the whole parse.
+fn synchronize(toks: &[Token], mut pos: usize) -> usize {
+    while let Some(tok) = toks.get(pos) {
+        match tok.kind {
+            TokKind::Semicolon | TokKind::RBrace => return pos + 1,
+            TokKind::Actor | TokKind::Fn | TokKind::Eof => return pos,
+            _ => pos += 1,
+        }
+    }
+    pos
+}
+
+fn parse_module_header(toks: &[Token], pos: usize) -> PResult<String> {
+    let (kw_res, errors, pos) = token(toks, pos, |k| matches!(k, TokKind::Module), "module");
+    let kw_tok = match kw_res {
+        Ok(t) => t,
+        Err(e) => return (Err(e), errors, pos),
+    };
+    let mut errors = errors;
+    let (first_res, errs, mut pos) = ident(toks, pos, "module ident");
+    errors.extend(errs);
+    let mut name = match first_res {
+        Ok(n) => n,
+        Err(e) => {
+            let labeled = e.with_label(Label::new(
+                kw_tok.span,
+                "while parsing this module declaration",
+            ));
+            errors.pop();
+            errors.push(labeled.clone());
+            return (Err(labeled), errors, pos);
+        }
+    };
+    while matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::Dot)) {
+        pos += 1;
+        let (id_res, errs, new_pos) = ident(toks, pos, "ident after dot");
+        errors.extend(errs);
+        pos = new_pos;
+        match id_res {
+            Ok(s) => {
+                name.push('.');
+                name.push_str(&s);
             }
-            None => break,
+            Err(_) => break,
         }
     }
-    Ok(m)
+    (Ok(name), errors, pos)
+}
+
+fn parse_effect(toks: &[Token], pos: usize) -> PResult<Effect> {
+    let (res, mut errors, pos) = ident(toks, pos, "effect ident");
+    match res {
+        Ok(s) => match Effect::from_ident(&s) {
+            Some(eff) => (Ok(eff), errors, pos),
+            None => {
+                let tok = toks.get(pos - 1).cloned().unwrap_or_else(|| eof_token(toks));
+                let err = ParseError::new(format!("unknown effect '{}'", s), &tok)
+                    .with_help("known effects are Db, Net, Now, Kms, Serial");
+                errors.push(err.clone());
+                (Err(err), errors, pos)
+            }
+        },
+        Err(e) => (Err(e), errors, pos),
+    }
+}
+
+/// Optional `effects <Effect (, Effect)*>` clause.
+fn parse_effects_clause(toks: &[Token], pos: usize) -> PResult<Vec<Effect>> {
+    if !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::Effects)) {
+        return (Ok(vec![]), vec![], pos);
+    }
+    sep_by(toks, pos + 1, &TokKind::Comma, parse_effect)
+}
+
+/// Recognize an effect-performing call starting at token index `at` —
+/// `<namespace>.<method>(` where `<namespace>` names a known effect
+/// namespace (`db`, `net`, `kms`, `serial`), or the bare call `now(` —
+/// returning the effect and the index of its opening paren.
+fn effect_call_at(toks: &[Token], at: usize) -> Option<(Effect, usize)> {
+    let namespace_effect = |s: &str| match s {
+        "db" => Some(Effect::Db),
+        "net" => Some(Effect::Net),
+        "kms" => Some(Effect::Kms),
+        "serial" => Some(Effect::Serial),
+        _ => None,
+    };
+    let TokKind::Ident(ns) = toks.get(at).map(|t| &t.kind)? else {
+        return None;
+    };
+    if let (Some(TokKind::Dot), Some(TokKind::Ident(_)), Some(TokKind::LParen)) = (
+        toks.get(at + 1).map(|t| &t.kind),
+        toks.get(at + 2).map(|t| &t.kind),
+        toks.get(at + 3).map(|t| &t.kind),
+    ) {
+        return namespace_effect(ns).map(|e| (e, at + 3));
+    }
+    if ns == "now" && matches!(toks.get(at + 1).map(|t| &t.kind), Some(TokKind::LParen)) {
+        return Some((Effect::Now, at + 1));
+    }
+    None
 }
 
-fn skip_block(toks: &[Token], i: &mut usize) -> Result<(), ParseError> {
-    if !matches!(toks.get(*i).map(|t| &t.kind), Some(TokKind::LBrace)) {
-        while !matches!(toks.get(*i).map(|t| &t.kind), Some(TokKind::LBrace))
-            && !matches!(toks.get(*i).map(|t| &t.kind), Some(TokKind::Eof))
+/// Parse a `{ ... }` body, recognizing effect-performing calls and returning
+/// the set of `Effect`s it performs (each keyed to the span of its first
+/// occurrence) along with the position just past the closing brace.
+fn parse_block(toks: &[Token], pos: usize) -> Result<(BTreeMap<Effect, Span>, usize), ParseError> {
+    let mut pos = pos;
+    if !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::LBrace)) {
+        while !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::LBrace))
+            && !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::Eof))
         {
-            *i += 1;
+            pos += 1;
         }
     }
+    let mut effects = BTreeMap::new();
     let mut depth = 0usize;
-    while *i < toks.len() {
-        match &toks[*i].kind {
-            TokKind::LBrace => {
-                depth += 1;
-            }
+    while pos < toks.len() {
+        // Only register the effect here; keep scanning token-by-token (rather
+        // than skipping past the call's argument list) so a nested
+        // effect-performing call, e.g. `db.query(net.fetch())`, is still
+        // found when we reach its own starting position.
+        if let Some((effect, _call_paren)) = effect_call_at(toks, pos) {
+            effects.entry(effect).or_insert(toks[pos].span);
+        }
+        match &toks[pos].kind {
+            TokKind::LBrace => depth += 1,
             TokKind::RBrace => {
                 depth -= 1;
                 if depth == 0 {
-                    *i += 1;
-                    return Ok(());
+                    return Ok((effects, pos + 1));
                 }
             }
-            TokKind::Eof => {
-                return Err(ParseError {
-                    msg: "unexpected EOF in block".into(),
-                    line: toks[*i].line,
-                    col: toks[*i].col,
-                })
-            }
+            TokKind::Eof => return Err(ParseError::new("unexpected EOF in block", &toks[pos])),
             _ => {}
         }
-        *i += 1;
+        pos += 1;
+    }
+    Err(ParseError::new("unterminated block", &eof_token(toks)))
+}
+
+fn parse_actor(toks: &[Token], pos: usize) -> PResult<Item> {
+    let (kw_res, errors, pos) = token(toks, pos, |k| matches!(k, TokKind::Actor), "'actor'");
+    if let Err(e) = kw_res {
+        return (Err(e), errors, pos);
+    }
+    let mut errors = errors;
+    let (name_res, errs, pos) = ident(toks, pos, "actor name");
+    errors.extend(errs);
+    let name = match name_res {
+        Ok(n) => n,
+        Err(e) => return (Err(e), errors, pos),
+    };
+    match parse_block(toks, pos) {
+        Ok((effects, new_pos)) => (Ok(Item::Actor { name, effects }), errors, new_pos),
+        Err(e) => {
+            errors.push(e.clone());
+            (Err(e), errors, pos)
+        }
+    }
+}
+
+fn parse_fn(toks: &[Token], pos: usize) -> PResult<Item> {
+    let (kw_res, errors, pos) = token(toks, pos, |k| matches!(k, TokKind::Fn), "'fn'");
+    if let Err(e) = kw_res {
+        return (Err(e), errors, pos);
+    }
+    let mut errors = errors;
+    let (name_res, errs, mut pos) = ident(toks, pos, "fn name");
+    errors.extend(errs);
+    let name = match name_res {
+        Ok(n) => n,
+        Err(e) => return (Err(e), errors, pos),
+    };
+    while !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::LBrace))
+        && !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::Eof))
+    {
+        pos += 1;
+    }
+    if !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::LBrace)) {
+        return (
+            Ok(Item::Func {
+                name,
+                effects: BTreeMap::new(),
+            }),
+            errors,
+            pos,
+        );
+    }
+    match parse_block(toks, pos) {
+        Ok((effects, new_pos)) => (Ok(Item::Func { name, effects }), errors, new_pos),
+        Err(e) => {
+            errors.push(e.clone());
+            (Err(e), errors, pos)
+        }
+    }
+}
+
+fn parse_item(toks: &[Token], pos: usize) -> PResult<Item> {
+    choice(&[parse_actor, parse_fn], toks, pos)
+}
+
+/// Parse a module, recovering from item-level errors so a single pass
+/// reports every diagnostic instead of stopping at the first one.
+pub fn parse(toks: &[Token]) -> Result<Module, Vec<ParseError>> {
+    let (name_res, mut errors, mut pos) = parse_module_header(toks, 0);
+    let name = match name_res {
+        Ok(n) => n,
+        Err(_) => return Err(errors),
+    };
+    let mut m = Module::new(name);
+
+    let (effects_res, errs, new_pos) = parse_effects_clause(toks, pos);
+    errors.extend(errs);
+    pos = new_pos;
+    match effects_res {
+        Ok(effs) => m.effects = effs,
+        Err(_) => return Err(errors),
+    }
+
+    while !matches!(toks.get(pos).map(|t| &t.kind), Some(TokKind::Eof)) {
+        let (item_res, errs, new_pos) = parse_item(toks, pos);
+        errors.extend(errs);
+        match item_res {
+            Ok(item) => {
+                m.items.push(item);
+                pos = new_pos;
+            }
+            Err(_) => {
+                pos = synchronize(toks, new_pos.max(pos + 1));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(m)
+    } else {
+        Err(errors)
     }
-    Err(ParseError {
-        msg: "unterminated block".into(),
-        line: 0,
-        col: 0,
-    })
 }
->>>>>>> origin/w5t1y7-codex/create-top-level-repo-layout
@@ -0,0 +1,84 @@
+use crate::{diagnostics, effects, lexer, parser};
+use anyhow::Result;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+fn history_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".weftc_history")
+}
+
+/// Whether `buf` still has unbalanced `{}`/`()`, an unterminated string, or
+/// hasn't closed a single item body back down to depth 0 yet, meaning the
+/// REPL should keep reading instead of parsing what it has. The last check
+/// matters for a module typed the natural way across several lines (`module
+/// demo`, then `actor A {` on its own line): with delimiter balance alone,
+/// the bare `module demo` line — having no braces at all — looks "balanced"
+/// and gets parsed on its own before the rest of the module is even typed.
+fn is_incomplete(buf: &str) -> bool {
+    let mut depth = 0i64;
+    let mut in_string = false;
+    let mut closed_a_body = false;
+    for c in buf.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' | '(' if !in_string => depth += 1,
+            '}' | ')' if !in_string => {
+                depth -= 1;
+                if depth == 0 && c == '}' {
+                    closed_a_body = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    in_string || depth > 0 || !closed_a_body
+}
+
+/// A line-oriented read-eval-print loop: accumulates lines until braces,
+/// parens, and strings balance out, then lexes/parses/prints the result.
+/// Submitted input is appended to a `.weftc_history` dotfile in `$HOME`.
+pub fn run() -> Result<()> {
+    let path = history_path();
+    let mut history = std::fs::read_to_string(&path).unwrap_or_default();
+
+    println!("weftc repl — type a module, blank line to exit");
+    let mut buf = String::new();
+    loop {
+        print!("{}", if buf.is_empty() { "weft> " } else { "... " });
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            break; // EOF (e.g. piped input, or Ctrl-D)
+        }
+        if line.trim().is_empty() && buf.is_empty() {
+            break;
+        }
+        buf.push_str(&line);
+        if is_incomplete(&buf) {
+            continue;
+        }
+
+        let src = std::mem::take(&mut buf);
+        history.push_str(&src);
+        if !history.ends_with('\n') {
+            history.push('\n');
+        }
+        std::fs::write(&path, &history)?;
+
+        match parser::parse(&lexer::lex(&src)) {
+            Ok(module) => {
+                let names: Vec<&str> = module.items.iter().map(|i| i.name()).collect();
+                println!("items: {:?}", names);
+                println!("effects: {:?}", effects::effects_graph(&module));
+            }
+            Err(errs) => {
+                for e in &errs {
+                    eprint!("{}", diagnostics::render(&src, &e.diagnostic()));
+                }
+            }
+        }
+    }
+    Ok(())
+}
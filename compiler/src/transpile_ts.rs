@@ -0,0 +1,19 @@
+use crate::ast::{Item, Module};
+
+/// Emit a minimal TypeScript stub mirroring a module's items: one exported
+/// function per `Item::Func`, one exported class per `Item::Actor`.
+pub fn emit_ts(module: &Module) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("// generated from module {}\n", module.name));
+    for item in &module.items {
+        match item {
+            Item::Func { name, .. } => {
+                out.push_str(&format!("export function {}(): void {{}}\n", name));
+            }
+            Item::Actor { name, .. } => {
+                out.push_str(&format!("export class {} {{}}\n", name));
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,31 @@
+use crate::ast::{Item, Module};
+use crate::effects::Effect;
+
+/// The WIT import path a declared effect grants.
+fn effect_import(effect: &Effect) -> &'static str {
+    match effect {
+        Effect::Db => "weft:effects/db",
+        Effect::Net => "weft:effects/net",
+        Effect::Now => "weft:effects/now",
+        Effect::Kms => "weft:effects/kms",
+        Effect::Serial => "weft:effects/serial",
+    }
+}
+
+/// Emit a WIT `world` describing a module's capability contract: one
+/// `import` per declared effect (the capabilities it needs granted) and one
+/// `export` function signature per `Item::Func` (the functions it provides).
+pub fn emit_wit(module: &Module) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("world {} {{\n", module.name.replace('.', "-")));
+    for effect in &module.effects {
+        out.push_str(&format!("    import {};\n", effect_import(effect)));
+    }
+    for item in &module.items {
+        if let Item::Func { name, .. } = item {
+            out.push_str(&format!("    export {}: func();\n", name));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
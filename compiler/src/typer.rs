@@ -1,23 +1,86 @@
 use crate::ast::Module;
+use crate::diagnostics::{Diagnostic, Severity};
 use crate::effects::Effect;
+use crate::lexer::Span;
+use std::collections::BTreeSet;
 
 #[derive(Debug)]
-pub struct TypeError(pub String);
+pub struct TypeError {
+    pub msg: String,
+    pub span: Span,
+    pub help: Option<String>,
+}
+
+impl TypeError {
+    fn new(msg: impl Into<String>, span: Span) -> Self {
+        Self {
+            msg: msg.into(),
+            span,
+            help: None,
+        }
+    }
+
+    fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut d = Diagnostic::error(self.msg.clone(), self.span);
+        if let Some(help) = &self.help {
+            d = d.with_help(help.clone());
+        }
+        d
+    }
+}
+
 impl std::fmt::Display for TypeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.msg)
     }
 }
 impl std::error::Error for TypeError {}
 
-pub fn typecheck(m: &Module) -> Result<(), TypeError> {
+/// Typecheck a module's declared effects against what its items actually
+/// perform. Returns declared-but-unused warnings on success; fails on the
+/// first call that performs an effect the module never declared.
+pub fn typecheck(m: &Module) -> Result<Vec<Diagnostic>, TypeError> {
     if m.name.is_empty() {
-        return Err(TypeError("empty module name".into()));
+        return Err(TypeError::new("empty module name", Span::new(0, 0)));
     }
-    for e in &m.effects {
-        match e {
-            Effect::Db | Effect::Net | Effect::Now | Effect::Kms | Effect::Serial => {}
+
+    let declared: BTreeSet<Effect> = m.effects.iter().cloned().collect();
+    let mut used = BTreeSet::new();
+    for item in &m.items {
+        used.extend(item.effects().keys().cloned());
+        let first_undeclared = item
+            .effects()
+            .iter()
+            .filter(|(effect, _)| !declared.contains(*effect))
+            .min_by_key(|(_, span)| span.start);
+        if let Some((effect, span)) = first_undeclared {
+            return Err(TypeError::new(
+                format!("unknown effect '{:?}' not declared by module", effect),
+                *span,
+            )
+            .with_help(format!(
+                "add '{:?}' to the module's `effects` list",
+                effect
+            )));
         }
     }
-    Ok(())
+
+    let warnings = declared
+        .difference(&used)
+        .map(|effect| {
+            Diagnostic {
+                severity: Severity::Warning,
+                message: format!("effect '{:?}' is declared but never performed", effect),
+                span: Span::new(0, 0),
+                labels: vec![],
+                help: Some(format!("remove '{:?}' from the module's `effects` list", effect)),
+            }
+        })
+        .collect();
+    Ok(warnings)
 }
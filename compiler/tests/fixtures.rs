@@ -0,0 +1,133 @@
+//! Corpus-based conformance harness: every `tests/fixtures/*.weft` file is
+//! lexed and parsed, then compared against sibling `.tokens`/`.ast` golden
+//! files. Run with `WEFT_BLESS=1` to (re)write the goldens for the current
+//! output.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use weftc::ast::{Item, Module};
+use weftc::effects::Effect;
+use weftc::lexer::{self, TokKind, Token};
+use weftc::parser;
+
+/// A `TokKind` shorn of its `Token`'s `line`/`col`/`span` — the only thing
+/// `assert_eq_ignore_span_tokens` compares.
+fn spanless_tokens(toks: &[Token]) -> Vec<&TokKind> {
+    toks.iter().map(|t| &t.kind).collect()
+}
+
+/// Compare two token streams ignoring position, only `TokKind` shape.
+fn assert_eq_ignore_span_tokens(actual: &[Token], expected: &[Token]) {
+    assert_eq!(
+        spanless_tokens(actual),
+        spanless_tokens(expected),
+        "token kinds differ"
+    );
+}
+
+/// A `Module`'s structural content with every `Span` dropped, keeping only
+/// what `assert_eq_ignore_span_module` compares.
+#[derive(Debug, PartialEq)]
+struct SpanlessItem {
+    kind: &'static str,
+    name: String,
+    effects: BTreeSet<Effect>,
+}
+
+#[derive(Debug, PartialEq)]
+struct SpanlessModule {
+    name: String,
+    effects: Vec<Effect>,
+    items: Vec<SpanlessItem>,
+}
+
+fn spanless_module(m: &Module) -> SpanlessModule {
+    SpanlessModule {
+        name: m.name.clone(),
+        effects: m.effects.clone(),
+        items: m
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Actor { name, effects } => SpanlessItem {
+                    kind: "actor",
+                    name: name.clone(),
+                    effects: effects.keys().cloned().collect(),
+                },
+                Item::Func { name, effects } => SpanlessItem {
+                    kind: "fn",
+                    name: name.clone(),
+                    effects: effects.keys().cloned().collect(),
+                },
+            })
+            .collect(),
+    }
+}
+
+/// Compare two modules ignoring every span, only item/effect structure.
+fn assert_eq_ignore_span_module(actual: &Module, expected: &Module) {
+    assert_eq!(spanless_module(actual), spanless_module(expected));
+}
+
+#[test]
+fn ignore_span_helpers_ignore_position() {
+    let src_a = "module a\nactor X { fn run() {} }";
+    let src_b = "module a\n\n\n  actor X { fn run() {} }";
+    let toks_a = lexer::lex(src_a);
+    let toks_b = lexer::lex(src_b);
+    assert_eq_ignore_span_tokens(&toks_a, &toks_b);
+
+    let m_a = parser::parse(&toks_a).expect("parse a");
+    let m_b = parser::parse(&toks_b).expect("parse b");
+    assert_eq_ignore_span_module(&m_a, &m_b);
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn check_or_bless(golden: &Path, actual: &str, bless: bool) {
+    if bless {
+        fs::write(golden, actual).unwrap_or_else(|e| panic!("write {}: {e}", golden.display()));
+        return;
+    }
+    let expected = fs::read_to_string(golden).unwrap_or_else(|_| {
+        panic!(
+            "missing golden {} — rerun with WEFT_BLESS=1 to create it",
+            golden.display()
+        )
+    });
+    assert_eq!(actual, expected, "{} mismatch", golden.display());
+}
+
+#[test]
+fn fixtures_match_goldens() {
+    let dir = fixtures_dir();
+    let bless = std::env::var("WEFT_BLESS").as_deref() == Ok("1");
+    let mut checked = 0usize;
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|_| panic!("{} must exist", dir.display()))
+        .map(|e| e.expect("readable fixture entry").path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("weft"))
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        checked += 1;
+        let src = fs::read_to_string(&path).expect("read fixture");
+        let toks = lexer::lex(&src);
+
+        let tokens_actual = format!("{:#?}\n", spanless_tokens(&toks));
+        check_or_bless(&path.with_extension("tokens"), &tokens_actual, bless);
+
+        let ast_actual = match parser::parse(&toks) {
+            Ok(module) => format!("{:#?}\n", spanless_module(&module)),
+            Err(errs) => format!("PARSE ERROR: {} error(s)\n", errs.len()),
+        };
+        check_or_bless(&path.with_extension("ast"), &ast_actual, bless);
+    }
+
+    assert!(checked > 0, "no .weft fixtures found under {}", dir.display());
+}
@@ -11,5 +11,5 @@ fn lex_parse_module_and_effects() {
     let m = parser::parse(&toks).expect("parse ok");
     assert_eq!(m.name, "textile.ingest");
     assert!(m.effects.len() == 2);
-    assert!(m.items.len() >= 1);
+    assert!(!m.items.is_empty());
 }
@@ -0,0 +1,42 @@
+use weftc::diagnostics::Severity;
+use weftc::{lexer, parser, typer};
+
+#[test]
+fn typecheck_rejects_effect_used_but_not_declared() {
+    let src = r#"
+        module bad.build
+        fn run() { db.query(1); }
+    "#;
+    let toks = lexer::lex(src);
+    let m = parser::parse(&toks).expect("parse ok");
+    let err = typer::typecheck(&m).expect_err("undeclared effect use must fail typecheck");
+    assert!(err.msg.contains("Db"));
+}
+
+#[test]
+fn typecheck_warns_on_effect_declared_but_never_performed() {
+    let src = r#"
+        module good.build
+        effects Db, Net
+        fn run() { db.query(1); }
+    "#;
+    let toks = lexer::lex(src);
+    let m = parser::parse(&toks).expect("parse ok");
+    let warnings = typer::typecheck(&m).expect("declared-but-unused is a warning, not an error");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert!(warnings[0].message.contains("Net"));
+}
+
+#[test]
+fn typecheck_is_clean_when_declared_effects_match_used_effects() {
+    let src = r#"
+        module good.build
+        effects Db
+        fn run() { db.query(1); }
+    "#;
+    let toks = lexer::lex(src);
+    let m = parser::parse(&toks).expect("parse ok");
+    let warnings = typer::typecheck(&m).expect("declared effect is used, should typecheck clean");
+    assert!(warnings.is_empty());
+}